@@ -0,0 +1,58 @@
+//! `Allocator` lets `Array` be backed by something other than the global allocator, e.g. an
+//! arena, a hugepage/mmap allocator, or a NUMA-pinned pool.
+
+use std::alloc::{alloc, alloc_zeroed, dealloc, Layout};
+use std::fmt;
+use std::ptr::NonNull;
+
+/// The requested allocation could not be satisfied.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+/// A source of raw memory.
+pub trait Allocator {
+    /// Allocate a block of memory described by `layout`.
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+    /// Allocate a zero-initialized block of memory described by `layout`.
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+    /// Deallocate a block of memory previously returned by `allocate`/`allocate_zeroed` on this
+    /// allocator with the same `layout`.
+    ///
+    /// # Safety
+    /// `ptr` must denote a block of memory currently allocated by this allocator with `layout`.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// The global heap allocator (`std::alloc::alloc`/`alloc_zeroed`/`dealloc`). This is the default
+/// `Array` backing store, matching today's behavior.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Global;
+
+unsafe impl Sync for Global {}
+unsafe impl Send for Global {}
+
+impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = unsafe { alloc(layout) };
+        let ptr = NonNull::new(ptr).ok_or(AllocError)?;
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = unsafe { alloc_zeroed(layout) };
+        let ptr = NonNull::new(ptr).ok_or(AllocError)?;
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        dealloc(ptr.as_ptr(), layout)
+    }
+}
@@ -0,0 +1,55 @@
+//! Error type returned by the fallible `try_*` constructors: allocation failure is reported as a
+//! `Result` instead of panicking or aborting.
+
+use std::fmt;
+
+use crate::allocator::AllocError;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum TryReserveErrorKind {
+    /// `size * size_of::<T>()` overflowed `usize`.
+    CapacityOverflow,
+    /// The computed size/alignment violated `Layout`'s invariants.
+    LayoutError,
+    /// The allocator could not satisfy the request (e.g. out of memory).
+    AllocError(AllocError),
+}
+
+/// The error type returned by `try_zero`, `try_new`, `try_new_from_template` and their `_in`
+/// variants when the requested allocation cannot be satisfied.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct TryReserveError {
+    kind: TryReserveErrorKind,
+}
+
+impl TryReserveError {
+    pub(crate) fn capacity_overflow() -> Self {
+        Self { kind: TryReserveErrorKind::CapacityOverflow }
+    }
+
+    pub(crate) fn layout_error() -> Self {
+        Self { kind: TryReserveErrorKind::LayoutError }
+    }
+}
+
+impl From<AllocError> for TryReserveError {
+    fn from(err: AllocError) -> Self {
+        Self { kind: TryReserveErrorKind::AllocError(err) }
+    }
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            TryReserveErrorKind::CapacityOverflow => {
+                write!(f, "memory allocation failed because the size overflowed")
+            }
+            TryReserveErrorKind::LayoutError => {
+                write!(f, "memory allocation failed because the computed layout is invalid")
+            }
+            TryReserveErrorKind::AllocError(err) => write!(f, "memory allocation failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for TryReserveError {}
@@ -0,0 +1,55 @@
+//! Lets `new`/`new_from_template` detect an all-zero element and fall back to the same
+//! `alloc_zeroed` fast path `zero` already gets, instead of writing every element one at a time.
+
+/// Types that can report whether a particular value is "all zero bytes". The default impl
+/// conservatively says no; the integer and float primitives and `Option<NonNull<T>>` override it.
+pub(crate) trait IsZero {
+    fn is_zero(&self) -> bool;
+}
+
+impl<T> IsZero for T {
+    default fn is_zero(&self) -> bool {
+        false
+    }
+}
+
+macro_rules! impl_is_zero {
+    ($($t:ty => $zero:expr),* $(,)?) => {
+        $(
+            impl IsZero for $t {
+                fn is_zero(&self) -> bool {
+                    *self == $zero
+                }
+            }
+        )*
+    };
+}
+
+impl_is_zero!(
+    u8 => 0, i8 => 0,
+    u16 => 0, i16 => 0,
+    u32 => 0, i32 => 0,
+    u64 => 0, i64 => 0,
+    usize => 0, isize => 0,
+);
+
+// `*self == 0.0` would treat `-0.0` as zero (IEEE-754 equality), silently turning a `-0.0`
+// template into `+0.0` via the `alloc_zeroed`/`write_bytes` fast path. Compare bit patterns
+// instead, so only the actual all-zero-bytes representation takes the fast path.
+impl IsZero for f32 {
+    fn is_zero(&self) -> bool {
+        self.to_bits() == 0
+    }
+}
+
+impl IsZero for f64 {
+    fn is_zero(&self) -> bool {
+        self.to_bits() == 0
+    }
+}
+
+impl<T> IsZero for Option<std::ptr::NonNull<T>> {
+    fn is_zero(&self) -> bool {
+        self.is_none()
+    }
+}
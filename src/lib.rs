@@ -30,25 +30,58 @@
 //! unsafely extended outside of this crate but for now it's private. As long as this type fits on the stack and
 //! the array fits in memory this should be allocatable.
 //!
+//! `Array<T, A>` is generic over its backing allocator `A` (defaulting to `Global`, the ordinary
+//! heap). Use the `_in` constructors (`zero_in`, `new_in`, `new_from_template_in`) to back an
+//! array with a custom `Allocator`, e.g. an arena, a hugepage/mmap allocator, or a NUMA-pinned pool.
+//!
 #![feature(const_generics)]
+#![feature(specialization)]
+#![feature(slice_ptr_get)]
+mod allocator;
+mod error;
+mod is_zero;
 mod zeroable;
 
-use std::alloc::{alloc, alloc_zeroed, dealloc, Layout};
+use std::alloc::Layout;
 use std::ops::{Index, IndexMut, Range};
+use std::ptr::NonNull;
 
+pub use crate::allocator::{AllocError, Allocator, Global};
+pub use crate::error::TryReserveError;
+use crate::is_zero::IsZero;
 use crate::zeroable::Zeroable;
 
-pub struct Array<T> {
+pub struct Array<T, A: Allocator = Global> {
     size: usize,
     ptr: *mut T,
+    alloc: A,
+}
+
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for Array<T, A>{}
+unsafe impl<T: Send, A: Allocator + Send> Send for Array<T, A>{}
+
+/// Drop guard used while initializing a freshly allocated buffer element-by-element. If
+/// initialization panics partway through, dropping this guard drops exactly the elements already
+/// written and deallocates the buffer. On success the caller `mem::forget`s the guard.
+struct InitGuard<'a, T, A: Allocator> {
+    ptr: *mut T,
+    initialized: usize,
+    layout: Layout,
+    alloc: &'a A,
 }
 
-unsafe impl<T> Sync for Array<T>{}
-unsafe impl<T> Send for Array<T>{}
+impl<'a, T, A: Allocator> Drop for InitGuard<'a, T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(self.ptr, self.initialized));
+            self.alloc.deallocate(NonNull::new_unchecked(self.ptr as *mut u8), self.layout);
+        }
+    }
+}
 
-impl<T> Array<T> {
+impl<T, A: Allocator> Array<T, A> {
     /// Create an immutable iterator over elements in Array.
-    pub fn iter<'a>(&'a self) -> ArrayIter<'a, T> {
+    pub fn iter<'a>(&'a self) -> ArrayIter<'a, T, A> {
         ArrayIter{
             arr: &self,
             iter: 0usize
@@ -68,65 +101,179 @@ impl<T> Array<T> {
     //pub fn to_slice_mut<'a>(&'a mut self) -> &'a mut [T] {
     //    slice::
 
+    /// Create a mutable iterator over elements in Array.
+    pub fn iter_mut<'a>(&'a mut self) -> ArrayIterMut<'a, T> {
+        ArrayIterMut{ iter: self.to_slice_mut().iter_mut() }
+    }
+
     /// The length of the array (number of elements T)
     pub fn len(&self) -> usize {
         self.size
     }
+
+    /// Overwrite every element with `f()`'s result, called once per element.
+    pub fn fill_with(&mut self, mut f: impl FnMut() -> T) {
+        for elem in self.to_slice_mut() {
+            *elem = f();
+        }
+    }
 }
 
-impl<T> Array<T>
+impl<T, A: Allocator> Array<T, A>
+  where T: Copy {
+    /// Overwrite every element with a copy of `value`. For `IsZero` values (the same primitives
+    /// `zero`/`new` fast-path) this lowers to a single `write_bytes` instead of per-element copies.
+    /// Requires `T: Copy` (rather than just `Clone`) so the `write_bytes` fast path, which
+    /// overwrites live elements without running their destructors, can't silently leak a `T` with
+    /// drop glue.
+    pub fn fill(&mut self, value: T) {
+        if value.is_zero() {
+            unsafe {
+                std::ptr::write_bytes(self.ptr, 0, self.size);
+            }
+            return;
+        }
+        for elem in self.to_slice_mut() {
+            *elem = value;
+        }
+    }
+}
+
+impl<T> Array<T, Global>
   where T: Zeroable {
     /// Extremely fast initialization if all you want is 0's. Note that your type must be Zeroable.
     /// The auto-Zeroable types are u8, i8, u16, i16, u32, i32, u64, i64, usize, isize, f32, f64.
     /// `std::Array`s also implement Zeroable allowing for types like `[u8; 1 << 25]`.
+    ///
+    /// Panics if `size * size_of::<T>()` overflows or the allocation fails; see `try_zero` for a
+    /// fallible version.
     pub fn zero(size: usize) -> Self {
+        Self::try_zero(size).unwrap()
+    }
+
+    /// Fallible version of `zero`: returns `Err` instead of panicking/aborting if the size
+    /// overflows or the allocator can't satisfy the request.
+    pub fn try_zero(size: usize) -> Result<Self, TryReserveError> {
+        Self::try_zero_in(size, Global)
+    }
+}
+
+impl<T, A: Allocator> Array<T, A>
+  where T: Zeroable {
+    /// Same as `zero`, but allocates from `alloc` instead of the global allocator.
+    pub fn zero_in(size: usize, alloc: A) -> Self {
+        Self::try_zero_in(size, alloc).unwrap()
+    }
+
+    /// Fallible version of `zero_in`.
+    pub fn try_zero_in(size: usize, alloc: A) -> Result<Self, TryReserveError> {
         let objsize = std::mem::size_of::<T>();
-        let layout = Layout::from_size_align(size * objsize, 8).unwrap();
-        let ptr = unsafe {
-            alloc_zeroed(layout) as *mut T
-        };
-        Self{size, ptr}
+        let bytes = size.checked_mul(objsize).ok_or_else(TryReserveError::capacity_overflow)?;
+        let layout = Layout::from_size_align(bytes, 8).map_err(|_| TryReserveError::layout_error())?;
+        let ptr = alloc.allocate_zeroed(layout)?.as_mut_ptr() as *mut T;
+        Ok(Self{size, ptr, alloc})
     }
 }
 
-impl<T> Array<T>
+impl<T> Array<T, Global>
   where T: Default + Copy {
     /// Easy initialization if all you want is your T's default instantiation
+    ///
+    /// Panics if `size * size_of::<T>()` overflows or the allocation fails; see `try_new` for a
+    /// fallible version.
     pub fn new(size: usize) -> Self {
+        Self::try_new(size).unwrap()
+    }
+
+    /// Fallible version of `new`: returns `Err` instead of panicking/aborting if the size
+    /// overflows or the allocator can't satisfy the request.
+    pub fn try_new(size: usize) -> Result<Self, TryReserveError> {
+        Self::try_new_in(size, Global)
+    }
+}
+
+impl<T, A: Allocator> Array<T, A>
+  where T: Default + Copy {
+    /// Same as `new`, but allocates from `alloc` instead of the global allocator.
+    pub fn new_in(size: usize, alloc: A) -> Self {
+        Self::try_new_in(size, alloc).unwrap()
+    }
+
+    /// Fallible version of `new_in`.
+    pub fn try_new_in(size: usize, alloc: A) -> Result<Self, TryReserveError> {
         let objsize = std::mem::size_of::<T>();
-        let layout = Layout::from_size_align(size * objsize, 8).unwrap();
-        let ptr = unsafe {
-            alloc(layout) as *mut T
-        };
+        let bytes = size.checked_mul(objsize).ok_or_else(TryReserveError::capacity_overflow)?;
+        let layout = Layout::from_size_align(bytes, 8).map_err(|_| TryReserveError::layout_error())?;
+        // `Default::default()` runs before anything is allocated: a user's `Default` impl is
+        // arbitrary code and may panic, and if it does there is no buffer yet to free.
         let default: T = Default::default();
+        // Fast path: an all-zero default collapses to a single `alloc_zeroed`, same as `zero`.
+        if default.is_zero() {
+            let ptr = alloc.allocate_zeroed(layout)?.as_mut_ptr() as *mut T;
+            return Ok(Self{size, ptr, alloc});
+        }
+        let ptr = alloc.allocate(layout)?.as_mut_ptr() as *mut T;
+        let mut guard = InitGuard{ ptr, initialized: 0, layout, alloc: &alloc };
         for i in 0..size {
             unsafe {
-                (*(ptr.wrapping_offset(i as isize))) = default;
+                std::ptr::write(ptr.wrapping_add(i), default);
             }
+            guard.initialized += 1;
         }
-        Self{size, ptr}
+        std::mem::forget(guard);
+        Ok(Self{size, ptr, alloc})
     }
 }
 
-impl<T> Array<T>
+impl<T> Array<T, Global>
   where T: Clone {
     /// More generic initialization instantiating all elements as copies of some template
+    ///
+    /// Panics if `size * size_of::<T>()` overflows or the allocation fails; see
+    /// `try_new_from_template` for a fallible version.
     pub fn new_from_template(size: usize, template: &T) -> Self {
+        Self::try_new_from_template(size, template).unwrap()
+    }
+
+    /// Fallible version of `new_from_template`: returns `Err` instead of panicking/aborting if
+    /// the size overflows or the allocator can't satisfy the request.
+    pub fn try_new_from_template(size: usize, template: &T) -> Result<Self, TryReserveError> {
+        Self::try_new_from_template_in(size, template, Global)
+    }
+}
+
+impl<T, A: Allocator> Array<T, A>
+  where T: Clone {
+    /// Same as `new_from_template`, but allocates from `alloc` instead of the global allocator.
+    pub fn new_from_template_in(size: usize, template: &T, alloc: A) -> Self {
+        Self::try_new_from_template_in(size, template, alloc).unwrap()
+    }
+
+    /// Fallible version of `new_from_template_in`.
+    pub fn try_new_from_template_in(size: usize, template: &T, alloc: A) -> Result<Self, TryReserveError> {
         let objsize = std::mem::size_of::<T>();
-        let layout = Layout::from_size_align(size * objsize, 8).unwrap();
-        let ptr = unsafe {
-            alloc(layout) as *mut T
-        };
+        let bytes = size.checked_mul(objsize).ok_or_else(TryReserveError::capacity_overflow)?;
+        let layout = Layout::from_size_align(bytes, 8).map_err(|_| TryReserveError::layout_error())?;
+        // Fast path: an all-zero template collapses to a single `alloc_zeroed`, same as `zero`.
+        if template.is_zero() {
+            let ptr = alloc.allocate_zeroed(layout)?.as_mut_ptr() as *mut T;
+            return Ok(Self{size, ptr, alloc});
+        }
+        let ptr = alloc.allocate(layout)?.as_mut_ptr() as *mut T;
+        let mut guard = InitGuard{ ptr, initialized: 0, layout, alloc: &alloc };
         for i in 0..size {
+            let elem = template.clone();
             unsafe {
-                (*(ptr.wrapping_offset(i as isize))) = template.clone();
+                std::ptr::write(ptr.wrapping_add(i), elem);
             }
+            guard.initialized += 1;
         }
-        Self{size, ptr}
+        std::mem::forget(guard);
+        Ok(Self{size, ptr, alloc})
     }
 }
 
-impl<T> Index<usize> for Array<T> {
+impl<T, A: Allocator> Index<usize> for Array<T, A> {
     type Output = T;
 
     fn index<'a>(&'a self, idx: usize) -> &'a Self::Output {
@@ -137,7 +284,7 @@ impl<T> Index<usize> for Array<T> {
     }
 }
 
-impl<T> IndexMut<usize> for Array<T> {
+impl<T, A: Allocator> IndexMut<usize> for Array<T, A> {
 
     fn index_mut<'a>(&'a mut self, idx: usize) -> &'a mut Self::Output {
 
@@ -147,7 +294,7 @@ impl<T> IndexMut<usize> for Array<T> {
     }
 }
 
-impl<T> Index<Range<usize>> for Array<T> {
+impl<T, A: Allocator> Index<Range<usize>> for Array<T, A> {
     type Output = [T];
 
     fn index<'a>(&'a self, idx: Range<usize>) -> &'a Self::Output {
@@ -155,27 +302,27 @@ impl<T> Index<Range<usize>> for Array<T> {
     }
 }
 
-impl<T> IndexMut<Range<usize>> for Array<T> {
+impl<T, A: Allocator> IndexMut<Range<usize>> for Array<T, A> {
 
     fn index_mut<'a>(&'a mut self, idx: Range<usize>) -> &'a mut Self::Output {
         &mut self.to_slice_mut()[idx]
     }
 }
 
-impl<T> Drop for Array<T> {
+impl<T, A: Allocator> Drop for Array<T, A> {
 
     fn drop(&mut self) {
         let objsize = std::mem::size_of::<T>();
         let layout = Layout::from_size_align(self.size * objsize, 8).unwrap();
         unsafe {
-            dealloc(self.ptr as *mut u8, layout);
+            self.alloc.deallocate(NonNull::new_unchecked(self.ptr as *mut u8), layout);
         }
     }
 }
 
-impl<'a, T> IntoIterator for &'a Array<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a Array<T, A> {
     type Item = &'a T;
-    type IntoIter = ArrayIter<'a, T>;
+    type IntoIter = ArrayIter<'a, T, A>;
     /// For now, you can only for loop iterate directly over the
     /// reference:
     /// ```
@@ -190,12 +337,12 @@ impl<'a, T> IntoIterator for &'a Array<T> {
     }
 }
 
-pub struct ArrayIter<'a, T> {
-    arr: &'a Array<T>,
+pub struct ArrayIter<'a, T, A: Allocator = Global> {
+    arr: &'a Array<T, A>,
     iter: usize
 }
 
-impl<'a, T> Iterator for ArrayIter<'a, T> {
+impl<'a, T, A: Allocator> Iterator for ArrayIter<'a, T, A> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -209,19 +356,242 @@ impl<'a, T> Iterator for ArrayIter<'a, T> {
     }
 }
 
-impl<'a, T> ExactSizeIterator for ArrayIter<'a, T> {
+impl<'a, T, A: Allocator> ExactSizeIterator for ArrayIter<'a, T, A> {
 
     fn len(&self) -> usize {
         self.arr.size - self.iter
     }
 }
 
+impl<'a, T, A: Allocator> IntoIterator for &'a mut Array<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = ArrayIterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Mutable iterator over elements in an `Array`, produced by `Array::iter_mut`. Thin wrapper
+/// around `slice::IterMut` over `to_slice_mut()`.
+pub struct ArrayIterMut<'a, T> {
+    iter: std::slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for ArrayIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ArrayIterMut<'a, T> {
+
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ArrayIterMut<'a, T> {
+
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T, A: Allocator> IntoIterator for Array<T, A> {
+    type Item = T;
+    type IntoIter = ArrayIntoIter<T, A>;
+
+    /// Consumes the array and returns an iterator that moves each `T` out, e.g. for
+    /// `Array<String>` or other non-`Copy` payloads where cloning would be wasteful:
+    /// ```
+    /// use arr::Array;
+    /// let arr: Array<usize> = Array::new_from_template(4, &5);
+    /// let total: usize = arr.into_iter().sum();
+    /// assert_eq!(total, 20);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        // Suppress `Array`'s own `Drop` so the buffer isn't freed out from under us: ownership of
+        // both the elements and the allocation is moving into the `ArrayIntoIter`.
+        let this = std::mem::ManuallyDrop::new(self);
+        let objsize = std::mem::size_of::<T>();
+        let layout = Layout::from_size_align(this.size * objsize, 8).unwrap();
+        ArrayIntoIter {
+            ptr: this.ptr,
+            start: 0,
+            end: this.size,
+            layout,
+            alloc: unsafe { std::ptr::read(&this.alloc) },
+        }
+    }
+}
+
+/// Owning iterator over an `Array<T, A>`, produced by `Array::into_iter`. Elements not yet
+/// yielded are dropped (and the backing allocation freed) when this iterator itself is dropped.
+pub struct ArrayIntoIter<T, A: Allocator = Global> {
+    ptr: *mut T,
+    start: usize,
+    end: usize,
+    layout: Layout,
+    alloc: A,
+}
+
+impl<T, A: Allocator> Iterator for ArrayIntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        let elem = unsafe { std::ptr::read(self.ptr.wrapping_add(self.start)) };
+        self.start += 1;
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.start;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for ArrayIntoIter<T, A> {
+
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for ArrayIntoIter<T, A> {
+
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(unsafe { std::ptr::read(self.ptr.wrapping_add(self.end)) })
+    }
+}
+
+impl<T, A: Allocator> Drop for ArrayIntoIter<T, A> {
+
+    fn drop(&mut self) {
+        unsafe {
+            let remainder = std::slice::from_raw_parts_mut(self.ptr.wrapping_add(self.start), self.end - self.start);
+            std::ptr::drop_in_place(remainder);
+            self.alloc.deallocate(NonNull::new_unchecked(self.ptr as *mut u8), self.layout);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use std::cell::Cell;
     use std::thread;
     use std::sync::{Arc, Mutex};
     use super::*;
 
+    /// `Allocator` that forwards to `Global` while recording calls, so tests can assert an
+    /// `Array<T, A>` actually routes through the `A` it was given.
+    #[derive(Default)]
+    struct TrackingAllocator {
+        allocs: Cell<usize>,
+        deallocs: Cell<usize>,
+        last_layout: Cell<Option<Layout>>,
+    }
+
+    impl Allocator for &TrackingAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.allocs.set(self.allocs.get() + 1);
+            self.last_layout.set(Some(layout));
+            Global.allocate(layout)
+        }
+
+        fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.allocs.set(self.allocs.get() + 1);
+            self.last_layout.set(Some(layout));
+            Global.allocate_zeroed(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.deallocs.set(self.deallocs.get() + 1);
+            Global.deallocate(ptr, layout);
+        }
+    }
+
+    /// `Allocator` whose `allocate`/`allocate_zeroed` always fail, so tests can exercise the
+    /// `AllocError` path of the `try_*` constructors without actually exhausting memory.
+    struct FailingAllocator;
+
+    impl Allocator for FailingAllocator {
+        fn allocate(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+
+        fn allocate_zeroed(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+
+        unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+            unreachable!("nothing this allocator hands out should ever be deallocated");
+        }
+    }
+
+    #[test]
+    fn test_try_zero_capacity_overflow() {
+        assert!(Array::<u64>::try_zero(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_try_new_capacity_overflow() {
+        assert!(Array::<u64>::try_new(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_try_new_from_template_capacity_overflow() {
+        assert!(Array::<u64>::try_new_from_template(usize::MAX, &5).is_err());
+    }
+
+    #[test]
+    fn test_try_zero_layout_error() {
+        // `size` fits in `usize` on its own, but rounding it up to an 8-byte-aligned `Layout`
+        // overflows `isize::MAX`.
+        assert!(Array::<u8>::try_zero(isize::MAX as usize).is_err());
+    }
+
+    #[test]
+    fn test_try_zero_in_alloc_error() {
+        assert!(Array::<u64, _>::try_zero_in(4, FailingAllocator).is_err());
+    }
+
+    #[test]
+    fn test_try_new_in_alloc_error() {
+        assert!(Array::<u64, _>::try_new_in(4, FailingAllocator).is_err());
+    }
+
+    #[test]
+    fn test_try_new_from_template_in_alloc_error() {
+        assert!(Array::<u64, _>::try_new_from_template_in(4, &5, FailingAllocator).is_err());
+    }
+
+    #[test]
+    fn test_custom_allocator() {
+        let tracker = TrackingAllocator::default();
+        let expected_layout = Layout::from_size_align(5 * std::mem::size_of::<usize>(), 8).unwrap();
+        {
+            let arr: Array<usize, &TrackingAllocator> = Array::new_from_template_in(5, &7, &tracker);
+            assert_eq!(arr[4], 7);
+            assert_eq!(tracker.allocs.get(), 1);
+            assert_eq!(tracker.last_layout.get(), Some(expected_layout));
+            assert_eq!(tracker.deallocs.get(), 0);
+        }
+        assert_eq!(tracker.deallocs.get(), 1);
+    }
+
     #[test]
     fn test_default() {
         let arr: Array<f32> = Array::new(4 << 20); // Uses 16 MB - much to large for a stack
@@ -298,6 +668,28 @@ mod test {
         assert_eq!(cnt, 5);
     }
 
+    #[test]
+    fn test_into_iter() {
+        let template = String::from("hi");
+        let arr: Array<String> = Array::new_from_template(5, &template);
+        let mut cnt = 0;
+        for s in arr {
+            assert_eq!(s, "hi");
+            cnt += 1;
+        }
+        assert_eq!(cnt, 5);
+    }
+
+    #[test]
+    fn test_into_iter_partial_drop() {
+        let arr: Array<usize> = Array::new_from_template(5, &5);
+        let mut iter = arr.into_iter();
+        assert_eq!(iter.next(), Some(5));
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.len(), 3);
+        // Remaining three elements are dropped (and the buffer freed) here.
+    }
+
     #[test]
     fn test_copy_from() {
         let mut arr: Array<usize> = Array::new_from_template(5, &5);
@@ -325,4 +717,44 @@ mod test {
         }
         assert_eq!(arr[4], 5);
     }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut arr: Array<usize> = Array::zero(5);
+        for i in arr.iter_mut() {
+            *i = 7;
+        }
+        assert_eq!(arr[4], 7);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut arr: Array<usize> = Array::zero(5);
+        arr.fill(9);
+        assert_eq!(arr[0], 9);
+        assert_eq!(arr[4], 9);
+    }
+
+    #[test]
+    fn test_negative_zero_template_preserved() {
+        let arr: Array<f64> = Array::new_from_template(4, &(-0.0f64));
+        assert!(arr[0].is_sign_negative());
+        assert_eq!(arr[0], 0.0);
+
+        let mut arr: Array<f64> = Array::zero(4);
+        arr.fill(-0.0f64);
+        assert!(arr[0].is_sign_negative());
+    }
+
+    #[test]
+    fn test_fill_with() {
+        let mut arr: Array<usize> = Array::zero(5);
+        let mut next = 0;
+        arr.fill_with(|| {
+            next += 1;
+            next
+        });
+        assert_eq!(arr[0], 1);
+        assert_eq!(arr[4], 5);
+    }
 }